@@ -0,0 +1,40 @@
+//! Source highlighting backends.
+//!
+//! Two backends implement [`Highlighter`]: [`treesitter_lexer`], which
+//! classifies tokens from an AST and can tell apart things a regex never
+//! could (a struct *definition* vs a struct *construction*), and
+//! [`regex_lexer`], a lighter regex-based fallback used whenever no grammar
+//! is registered for a language.
+
+mod regex_lexer;
+mod treesitter_lexer;
+
+use crate::scope::Scope;
+
+/// A single highlighted span of source text, as a byte range into the
+/// original source string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub start: usize,
+    pub end: usize,
+    pub scope: Scope,
+}
+
+/// Something that can turn source text into a list of [`Token`]s.
+pub trait Highlighter {
+    /// Classify `source` into scoped tokens, in byte order, non-overlapping.
+    fn highlight(&self, source: &str) -> Vec<Token>;
+}
+
+/// Picks the best available highlighter for a file, based on its
+/// extension: a tree-sitter grammar if one is registered for the
+/// language, otherwise the regex fallback.
+pub fn highlighter_for_path(path: &std::path::Path) -> Box<dyn Highlighter> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("rs") => match treesitter_lexer::TreeSitterHighlighter::rust() {
+            Some(hl) => Box::new(hl),
+            None => Box::new(regex_lexer::RegexHighlighter::rust()),
+        },
+        _ => Box::new(regex_lexer::RegexHighlighter::rust()),
+    }
+}
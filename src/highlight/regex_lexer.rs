@@ -0,0 +1,164 @@
+//! Dependency-light regex fallback highlighter.
+//!
+//! Used whenever [`super::treesitter_lexer`] has no grammar registered
+//! for a language. It can't tell a definition from a use, so every
+//! identifier-shaped scope it emits is the coarsest one available
+//! (`Scope::Type`, `Scope::Identifier`, ...).
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::{Highlighter, Token};
+use crate::scope::Scope;
+
+const KEYWORDS: &[&str] = &[
+    "as", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn",
+    "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe",
+    "use", "where", "while", "async", "await",
+];
+
+const BUILTIN_TYPES: &[&str] = &[
+    "bool", "char", "str", "String", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16",
+    "i32", "i64", "i128", "isize", "f32", "f64", "Option", "Result", "Vec", "Box", "Self",
+];
+
+static TOKEN_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r#"(?P<comment>//[^\n]*)"#,
+        r#"|(?P<string>"(?:\\.|[^"\\])*")"#,
+        r#"|(?P<lifetime>'[A-Za-z_][A-Za-z0-9_]*)"#,
+        r#"|(?P<number>\b[0-9][0-9_]*(?:\.[0-9_]+)?(?:[A-Za-z0-9_]*)?)"#,
+        r#"|(?P<macro>\b[A-Za-z_][A-Za-z0-9_]*!)"#,
+        r#"|(?P<ident>\b[A-Za-z_][A-Za-z0-9_]*\b)"#,
+        r#"|(?P<op>[-+*/%=<>!&|^~?:;,.@#\[\]\(\)\{\}]+)"#,
+    ))
+    .expect("static token regex is valid")
+});
+
+/// Regex-based fallback highlighter, tuned for Rust-like syntax.
+pub struct RegexHighlighter;
+
+impl RegexHighlighter {
+    pub fn rust() -> Self {
+        RegexHighlighter
+    }
+
+    fn classify_ident(word: &str) -> Scope {
+        if KEYWORDS.contains(&word) {
+            Scope::Keyword
+        } else if BUILTIN_TYPES.contains(&word) {
+            Scope::TypeBuiltin
+        } else if word.starts_with(char::is_uppercase) {
+            Scope::Type
+        } else {
+            Scope::Identifier
+        }
+    }
+}
+
+impl Highlighter for RegexHighlighter {
+    fn highlight(&self, source: &str) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        for caps in TOKEN_RE.captures_iter(source) {
+            let (scope, m) = if let Some(m) = caps.name("comment") {
+                (Scope::Comment, m)
+            } else if let Some(m) = caps.name("string") {
+                (Scope::String, m)
+            } else if let Some(m) = caps.name("lifetime") {
+                (Scope::Lifetime, m)
+            } else if let Some(m) = caps.name("number") {
+                (Scope::Number, m)
+            } else if let Some(m) = caps.name("macro") {
+                // The regex crate has no lookahead, so `\w+!` also
+                // matches the `a` in `a!=`/`a!==`; catch that here and
+                // split it back into an identifier plus an operator
+                // rather than misreading it as a macro invocation.
+                if source.as_bytes().get(m.end()) == Some(&b'=') {
+                    let name_end = m.end() - 1;
+                    tokens.push(Token {
+                        start: m.start(),
+                        end: name_end,
+                        scope: Self::classify_ident(&source[m.start()..name_end]),
+                    });
+                    tokens.push(Token {
+                        start: name_end,
+                        end: m.end(),
+                        scope: Scope::Operator,
+                    });
+                    continue;
+                }
+                (Scope::Macro, m)
+            } else if let Some(m) = caps.name("ident") {
+                (Self::classify_ident(m.as_str()), m)
+            } else if let Some(m) = caps.name("op") {
+                (Scope::Operator, m)
+            } else {
+                continue;
+            };
+            tokens.push(Token {
+                start: m.start(),
+                end: m.end(),
+                scope,
+            });
+        }
+        tokens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_keywords_types_and_strings() {
+        let hl = RegexHighlighter::rust();
+        let tokens = hl.highlight(r#"pub fn new() -> String { "hi".to_string() }"#);
+
+        let scope_of = |text: &str, source: &str| {
+            tokens
+                .iter()
+                .find(|t| &source[t.start..t.end] == text)
+                .map(|t| t.scope)
+        };
+        let src = r#"pub fn new() -> String { "hi".to_string() }"#;
+        assert_eq!(scope_of("pub", src), Some(Scope::Keyword));
+        assert_eq!(scope_of("fn", src), Some(Scope::Keyword));
+        assert_eq!(scope_of("String", src), Some(Scope::TypeBuiltin));
+        assert_eq!(scope_of(r#""hi""#, src), Some(Scope::String));
+    }
+
+    #[test]
+    fn does_not_mistake_inequality_for_a_macro_invocation() {
+        let hl = RegexHighlighter::rust();
+        let src = "if a!=b:";
+        let tokens = hl.highlight(src);
+
+        assert!(
+            !tokens.iter().any(|t| t.scope == Scope::Macro),
+            "`!=` should never be tokenized as a macro invocation"
+        );
+        let scope_of = |text: &str| {
+            tokens
+                .iter()
+                .find(|t| &src[t.start..t.end] == text)
+                .map(|t| t.scope)
+        };
+        assert_eq!(scope_of("a"), Some(Scope::Identifier));
+        assert_eq!(scope_of("!"), Some(Scope::Operator));
+        assert_eq!(scope_of("="), Some(Scope::Operator));
+        assert_eq!(scope_of("b"), Some(Scope::Identifier));
+    }
+
+    #[test]
+    fn does_not_distinguish_definitions_from_calls() {
+        let hl = RegexHighlighter::rust();
+        let tokens = hl.highlight("fn find_max() {} find_max();");
+        let idents: Vec<_> = tokens
+            .iter()
+            .filter(|t| t.scope == Scope::Identifier)
+            .collect();
+        // Both the definition and the call land in the same coarse scope.
+        assert_eq!(idents.len(), 2);
+    }
+}
@@ -0,0 +1,255 @@
+//! Tree-sitter–backed highlighter.
+//!
+//! Unlike [`super::regex_lexer`], this backend walks a real syntax tree,
+//! so it can tell apart things that look identical to a regex: a struct
+//! *definition* (`struct Config`) from a struct *construction*
+//! (`Self { .. }`), a function *definition* from a function *call*, and
+//! a plain struct field from an enum struct-variant field.
+
+use tree_sitter::{Language, Node, Parser};
+
+use super::{Highlighter, Token};
+use crate::scope::Scope;
+
+/// Keywords recognized as leaf tokens in the Rust grammar. Anonymous
+/// tokens in tree-sitter take their `kind()` from the literal text, so
+/// this doubles as the keyword table for leaf classification.
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+    "pub", "ref", "return", "static", "struct", "super", "trait", "type", "unsafe", "use",
+    "where", "while",
+];
+
+const PUNCTUATION: &[&str] = &["(", ")", "{", "}", "[", "]", ",", ";", "::", ":", "->", "=>", "."];
+
+/// Tree-sitter highlighter for a single language.
+pub struct TreeSitterHighlighter {
+    language: Language,
+}
+
+impl TreeSitterHighlighter {
+    /// Builds the Rust grammar highlighter, or `None` if the grammar
+    /// couldn't be loaded (it never fails in practice; the `Option`
+    /// exists so callers can fall back to the regex lexer uniformly for
+    /// any language, including ones with no grammar registered at all).
+    pub fn rust() -> Option<Self> {
+        Some(TreeSitterHighlighter {
+            language: tree_sitter_rust::language(),
+        })
+    }
+}
+
+impl Highlighter for TreeSitterHighlighter {
+    fn highlight(&self, source: &str) -> Vec<Token> {
+        let mut parser = Parser::new();
+        if parser.set_language(self.language).is_err() {
+            return Vec::new();
+        }
+        let Some(tree) = parser.parse(source, None) else {
+            return Vec::new();
+        };
+
+        let mut tokens = Vec::new();
+        collect_leaves(tree.root_node(), source, &mut tokens);
+        tokens
+    }
+}
+
+fn collect_leaves(node: Node, source: &str, out: &mut Vec<Token>) {
+    // `lifetime` wraps its `'` and the name in a named `identifier` child,
+    // but themes want the whole `'a` treated as one atomic token. Likewise
+    // `string_literal` has its quotes as children; the whole literal is
+    // one `String` token.
+    let is_atomic =
+        node.child_count() == 0 || node.kind() == "lifetime" || node.kind() == "string_literal";
+    if is_atomic {
+        if node.start_byte() < node.end_byte() {
+            out.push(Token {
+                start: node.start_byte(),
+                end: node.end_byte(),
+                scope: classify(node, source),
+            });
+        }
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_leaves(child, source, out);
+    }
+}
+
+fn classify(node: Node, source: &str) -> Scope {
+    match node.kind() {
+        "line_comment" | "block_comment" => return Scope::Comment,
+        "string_literal" | "raw_string_literal" | "char_literal" => return Scope::String,
+        "integer_literal" | "float_literal" => return Scope::Number,
+        "primitive_type" => return Scope::TypeBuiltin,
+        "lifetime" => return Scope::Lifetime,
+        "self" | "true" | "false" | "mutable_specifier" => return Scope::Keyword,
+        "identifier" => return classify_identifier(node, source),
+        "type_identifier" => return classify_type_identifier(node),
+        "field_identifier" => return classify_field_identifier(node),
+        "shorthand_field_identifier" => return Scope::Identifier,
+        kind if KEYWORDS.contains(&kind) => return Scope::Keyword,
+        "!" if is_macro_bang(node) => return Scope::Macro,
+        kind if PUNCTUATION.contains(&kind) => return Scope::Punctuation,
+        _ => {}
+    }
+    if node.kind().chars().all(|c| !c.is_alphanumeric() && c != '_') {
+        return Scope::Operator;
+    }
+    Scope::Plain
+}
+
+fn is_macro_bang(node: Node) -> bool {
+    node.parent().is_some_and(|p| p.kind() == "macro_invocation")
+}
+
+fn is_field(parent: Node, field: &str, node: Node) -> bool {
+    parent.child_by_field_name(field) == Some(node)
+}
+
+fn classify_identifier(node: Node, source: &str) -> Scope {
+    let Some(parent) = node.parent() else {
+        return Scope::Identifier;
+    };
+    match parent.kind() {
+        "function_item" if is_field(parent, "name", node) => Scope::FunctionDefinition,
+        "call_expression" if is_field(parent, "function", node) => Scope::FunctionCall,
+        "macro_invocation" if is_field(parent, "macro", node) => Scope::Macro,
+        "scoped_identifier" => classify_scoped_segment(parent, node, source),
+        "scoped_type_identifier" if is_field(parent, "path", node) => {
+            if starts_uppercase(node, source) {
+                Scope::Type
+            } else {
+                Scope::Identifier
+            }
+        }
+        _ => Scope::Identifier,
+    }
+}
+
+/// `Config::new` parses as a `scoped_identifier` with no distinction
+/// between the type-ish path segment and the value-ish name segment, so
+/// this recovers it heuristically: a capitalized path segment is
+/// treated as a type, and the final segment is a call target if its
+/// enclosing `scoped_identifier` is itself the callee of a call.
+fn classify_scoped_segment(scoped: Node, node: Node, source: &str) -> Scope {
+    if is_field(scoped, "path", node) {
+        return if starts_uppercase(node, source) {
+            Scope::Type
+        } else {
+            Scope::Identifier
+        };
+    }
+    // `node` is the `name` segment.
+    let is_call_target = scoped
+        .parent()
+        .is_some_and(|gp| gp.kind() == "call_expression" && is_field(gp, "function", scoped));
+    if is_call_target {
+        Scope::FunctionCall
+    } else {
+        Scope::Identifier
+    }
+}
+
+fn starts_uppercase(node: Node, source: &str) -> bool {
+    node.utf8_text(source.as_bytes())
+        .ok()
+        .and_then(|text| text.chars().next())
+        .is_some_and(|c| c.is_uppercase())
+}
+
+fn classify_type_identifier(node: Node) -> Scope {
+    let Some(parent) = node.parent() else {
+        return Scope::Type;
+    };
+    match parent.kind() {
+        "struct_item" | "enum_item" | "trait_item" if is_field(parent, "name", node) => {
+            Scope::TypeDefinition
+        }
+        "trait_bounds" => Scope::TraitBound,
+        _ => Scope::Type,
+    }
+}
+
+fn classify_field_identifier(node: Node) -> Scope {
+    let Some(parent) = node.parent() else {
+        return Scope::Identifier;
+    };
+    if parent.kind() == "field_declaration" && is_field(parent, "name", node) {
+        return if nearest_ancestor_is_enum_variant(parent) {
+            Scope::VariantField
+        } else {
+            Scope::Identifier
+        };
+    }
+    Scope::Identifier
+}
+
+fn nearest_ancestor_is_enum_variant(node: Node) -> bool {
+    let mut current = node.parent();
+    while let Some(n) = current {
+        match n.kind() {
+            "enum_variant" => return true,
+            "struct_item" => return false,
+            _ => current = n.parent(),
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scope_of(source: &str, text: &str) -> Option<Scope> {
+        let hl = TreeSitterHighlighter::rust().unwrap();
+        hl.highlight(source)
+            .into_iter()
+            .find(|t| &source[t.start..t.end] == text)
+            .map(|t| t.scope)
+    }
+
+    #[test]
+    fn distinguishes_struct_definition_from_construction() {
+        let src = "struct Config { name: String }\nfn f() { Self { name: s } }";
+        assert_eq!(
+            scope_of(src, "Config"),
+            Some(Scope::TypeDefinition),
+            "name at the struct_item is a definition"
+        );
+        assert_eq!(
+            scope_of(src, "Self"),
+            Some(Scope::Type),
+            "name at a struct_expression is a reference, not a definition"
+        );
+    }
+
+    #[test]
+    fn distinguishes_function_definition_from_call() {
+        let src = "fn find_max() {}\nfn g() { find_max(); }";
+        let tokens = TreeSitterHighlighter::rust().unwrap().highlight(src);
+        let scopes: Vec<Scope> = tokens
+            .iter()
+            .filter(|t| &src[t.start..t.end] == "find_max")
+            .map(|t| t.scope)
+            .collect();
+        assert_eq!(scopes, vec![Scope::FunctionDefinition, Scope::FunctionCall]);
+    }
+
+    #[test]
+    fn tags_enum_variant_fields_but_not_plain_struct_fields() {
+        let src = "struct Config { name: String }\nenum Status { Pending { reason: String } }";
+        assert_eq!(scope_of(src, "name"), Some(Scope::Identifier));
+        assert_eq!(scope_of(src, "reason"), Some(Scope::VariantField));
+    }
+
+    #[test]
+    fn tags_lifetimes_and_trait_bounds() {
+        let src = "fn find_max<T: Ord>(items: &[T]) -> Option<&T> { items.iter().max() }\nfn f<'a>(x: &'a str) {}";
+        assert_eq!(scope_of(src, "Ord"), Some(Scope::TraitBound));
+        assert_eq!(scope_of(src, "'a"), Some(Scope::Lifetime));
+    }
+}
@@ -0,0 +1,75 @@
+//! ultra: a terminal syntax highlighter.
+
+mod highlight;
+mod picker;
+mod render;
+mod scope;
+mod theme;
+mod viewer;
+
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+
+use theme::{Palette, Theme};
+
+fn main() -> Result<()> {
+    let mut theme_name: Option<String> = None;
+    let mut paths: Vec<PathBuf> = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--theme" {
+            theme_name = Some(args.next().context("--theme requires a value")?);
+        } else {
+            paths.push(PathBuf::from(arg));
+        }
+    }
+    let Some(first) = paths.first() else {
+        bail!("usage: ultra [--theme <name-or-path>] <file> | <dir> | <file>...");
+    };
+
+    let theme = match theme_name {
+        Some(name) => Theme::resolve(&name)?,
+        None => Theme::default(),
+    };
+    let palette = Palette::new(theme);
+
+    let target = if paths.len() == 1 && first.is_dir() {
+        let candidates = picker::discover_dir(first);
+        pick(candidates, &palette)?
+    } else if paths.len() > 1 {
+        pick(paths, &palette)?
+    } else {
+        Some(first.clone())
+    };
+
+    let Some(path) = target else {
+        return Ok(());
+    };
+    open(&path, palette)
+}
+
+/// Runs the fuzzy picker over `candidates` and returns the chosen path,
+/// or `None` if the user aborted without selecting one.
+fn pick(candidates: Vec<PathBuf>, palette: &Palette) -> Result<Option<PathBuf>> {
+    if !std::io::stdout().is_terminal() {
+        bail!("the picker needs an interactive terminal");
+    }
+    picker::Picker::new(candidates, palette.clone()).run()
+}
+
+fn open(path: &PathBuf, palette: Palette) -> Result<()> {
+    let source =
+        std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+
+    let highlighter = highlight::highlighter_for_path(path);
+    let tokens = highlighter.highlight(&source);
+
+    if std::io::stdout().is_terminal() {
+        viewer::Viewer::new(source, tokens, palette).run()
+    } else {
+        print!("{}", render::render(&source, &tokens, &palette));
+        Ok(())
+    }
+}
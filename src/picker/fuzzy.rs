@@ -0,0 +1,75 @@
+//! Subsequence fuzzy matching, fzf-style: every character of the query
+//! must appear in the candidate in order, case-insensitively, but not
+//! necessarily contiguously. Kept dependency-free and pure so it can be
+//! unit tested without a terminal.
+
+/// Scores `candidate` against `query`, or `None` if `query` isn't a
+/// subsequence of `candidate`. Higher scores are better matches.
+///
+/// The score rewards matches that start right after a path separator
+/// (so typing "main" ranks `src/main.rs` above `domain.rs`),
+/// consecutive character runs, and shorter candidates.
+pub fn score(query: &str, candidate: &str) -> Option<i32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    let cand: Vec<char> = candidate.chars().collect();
+    let cand_lower: Vec<char> = candidate.to_lowercase().chars().collect();
+
+    let mut qi = 0;
+    let mut total = 0i32;
+    let mut prev_match: Option<usize> = None;
+
+    for (ci, &c) in cand_lower.iter().enumerate() {
+        if qi >= query.len() {
+            break;
+        }
+        if c != query[qi] {
+            continue;
+        }
+        let mut bonus = 1;
+        if ci == 0 || matches!(cand[ci - 1], '/' | '\\' | '_' | '-' | '.') {
+            bonus += 8;
+        }
+        if prev_match == Some(ci.wrapping_sub(1)) {
+            bonus += 4;
+        }
+        total += bonus;
+        prev_match = Some(ci);
+        qi += 1;
+    }
+
+    if qi < query.len() {
+        return None;
+    }
+    // Slight preference for shorter candidates among equal bonuses.
+    Some(total * 100 - cand.len() as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_non_subsequences() {
+        assert_eq!(score("xyz", "main.rs"), None);
+    }
+
+    #[test]
+    fn matches_non_contiguous_chars_as_subsequence() {
+        assert!(score("man", "main.rs").is_some());
+    }
+
+    #[test]
+    fn ranks_path_boundary_matches_above_mid_word_matches() {
+        let boundary = score("main", "src/main.rs").unwrap();
+        let midword = score("main", "domain.rs").unwrap();
+        assert!(boundary > midword);
+    }
+
+    #[test]
+    fn empty_query_matches_everything_with_zero_score() {
+        assert_eq!(score("", "anything"), Some(0));
+    }
+}
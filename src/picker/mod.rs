@@ -0,0 +1,251 @@
+//! Fuzzy file picker: a filtered, scrollable list on the left with a
+//! live highlighted preview of the selection on the right. Selecting an
+//! entry hands its path back to the caller, which loads it into the
+//! main [`crate::viewer::Viewer`].
+
+mod fuzzy;
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::{cursor, execute, queue, terminal};
+
+use crate::highlight;
+use crate::theme::Palette;
+
+/// Walks `root` recursively and returns every regular file found,
+/// skipping hidden entries (dotfiles/dotdirs) like `.git`.
+pub fn discover_dir(root: &Path) -> Vec<PathBuf> {
+    let mut out = Vec::new();
+    walk(root, &mut out);
+    out.sort();
+    out
+}
+
+fn walk(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_hidden = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with('.'));
+        if is_hidden {
+            continue;
+        }
+        if path.is_dir() {
+            walk(&path, out);
+        } else if path.is_file() {
+            out.push(path);
+        }
+    }
+}
+
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// A cached highlighted preview, recomputed when the selection moves or
+/// the query changes, never on a plain redraw, so redraws stay cheap.
+struct Preview {
+    path: PathBuf,
+    lines: Vec<String>,
+}
+
+impl Preview {
+    fn load(path: &Path, palette: &Palette) -> Self {
+        let lines = match std::fs::read_to_string(path) {
+            Ok(source) => {
+                let tokens = highlight::highlighter_for_path(path).highlight(&source);
+                let rendered = crate::render::render(&source, &tokens, palette);
+                rendered.lines().map(str::to_string).collect()
+            }
+            Err(err) => vec![format!("<{}>", err)],
+        };
+        Preview {
+            path: path.to_path_buf(),
+            lines,
+        }
+    }
+}
+
+pub struct Picker {
+    candidates: Vec<PathBuf>,
+    query: String,
+    /// Indices into `candidates`, filtered and sorted best-match-first.
+    filtered: Vec<usize>,
+    selected: usize,
+    /// Index into `filtered` of the first entry drawn in the list pane.
+    scroll: usize,
+    preview: Option<Preview>,
+    palette: Palette,
+}
+
+impl Picker {
+    pub fn new(candidates: Vec<PathBuf>, palette: Palette) -> Self {
+        let filtered = (0..candidates.len()).collect();
+        let mut picker = Picker {
+            candidates,
+            query: String::new(),
+            filtered,
+            selected: 0,
+            scroll: 0,
+            preview: None,
+            palette,
+        };
+        picker.refresh_preview();
+        picker
+    }
+
+    /// Runs the picker's event loop and returns the chosen path, or
+    /// `None` if the user aborted.
+    pub fn run(&mut self) -> Result<Option<PathBuf>> {
+        let _guard = TerminalGuard::enter()?;
+        loop {
+            self.draw()?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Esc => return Ok(None),
+                KeyCode::Enter => {
+                    return Ok(self.current_path().cloned());
+                }
+                KeyCode::Backspace => {
+                    self.query.pop();
+                    self.refilter();
+                }
+                KeyCode::Char(c) => {
+                    self.query.push(c);
+                    self.refilter();
+                }
+                KeyCode::Down => self.move_selection(1),
+                KeyCode::Up => self.move_selection(-1),
+                _ => {}
+            }
+        }
+    }
+
+    fn current_path(&self) -> Option<&PathBuf> {
+        self.filtered.get(self.selected).map(|&i| &self.candidates[i])
+    }
+
+    fn refilter(&mut self) {
+        let mut scored: Vec<(i32, usize)> = self
+            .candidates
+            .iter()
+            .enumerate()
+            .filter_map(|(i, path)| {
+                fuzzy::score(&self.query, &path.to_string_lossy()).map(|s| (s, i))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        self.filtered = scored.into_iter().map(|(_, i)| i).collect();
+        self.selected = 0;
+        self.scroll = 0;
+        self.refresh_preview();
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let max = self.filtered.len().saturating_sub(1);
+        self.selected = (self.selected as isize + delta).clamp(0, max as isize) as usize;
+        self.scroll_to_selection();
+        self.refresh_preview();
+    }
+
+    /// Rows available for the list/preview panes, excluding the status row.
+    fn list_height(&self) -> usize {
+        terminal::size().map(|(_, h)| h.saturating_sub(1) as usize).unwrap_or(23)
+    }
+
+    fn scroll_to_selection(&mut self) {
+        let height = self.list_height();
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if self.selected >= self.scroll + height {
+            self.scroll = self.selected + 1 - height;
+        }
+    }
+
+    fn refresh_preview(&mut self) {
+        self.preview = self
+            .current_path()
+            .cloned()
+            .map(|p| Preview::load(&p, &self.palette));
+    }
+
+    fn draw(&self) -> Result<()> {
+        let mut stdout = io::stdout();
+        let (width, height) = terminal::size().unwrap_or((80, 24));
+        let list_width = (width / 3).max(20);
+        let rows = height.saturating_sub(1);
+
+        for row in 0..rows {
+            queue!(
+                stdout,
+                cursor::MoveTo(0, row),
+                terminal::Clear(terminal::ClearType::CurrentLine)
+            )?;
+            let entry = self.scroll + row as usize;
+            let list_cell = self
+                .filtered
+                .get(entry)
+                .map(|&i| self.candidates[i].to_string_lossy().to_string())
+                .unwrap_or_default();
+            let marker = if entry == self.selected { ">" } else { " " };
+            let list_cell = truncate(&format!("{marker} {list_cell}"), list_width as usize);
+            write!(stdout, "{:<width$}", list_cell, width = list_width as usize)?;
+
+            queue!(stdout, cursor::MoveTo(list_width + 1, row))?;
+            if let Some(line) = self
+                .preview
+                .as_ref()
+                .and_then(|p| p.lines.get(row as usize))
+            {
+                write!(stdout, "{line}")?;
+            }
+        }
+
+        queue!(
+            stdout,
+            cursor::MoveTo(0, rows),
+            terminal::Clear(terminal::ClearType::CurrentLine)
+        )?;
+        let preview_name = self
+            .preview
+            .as_ref()
+            .map(|p| p.path.display().to_string())
+            .unwrap_or_default();
+        write!(stdout, "> {}   [{}]", self.query, preview_name)?;
+        stdout.flush()?;
+        Ok(())
+    }
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        s.to_string()
+    } else {
+        s.chars().take(max.saturating_sub(1)).collect::<String>() + "…"
+    }
+}
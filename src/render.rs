@@ -0,0 +1,27 @@
+//! Renders highlighted [`Token`]s to an ANSI terminal using a [`Palette`].
+
+use crate::highlight::Token;
+use crate::theme::Palette;
+
+/// Renders `source` to a string of ANSI-colored text using `tokens`,
+/// styled according to `palette`.
+///
+/// Gaps between tokens (whitespace, anything the lexer didn't classify)
+/// are copied through unstyled.
+pub fn render(source: &str, tokens: &[Token], palette: &Palette) -> String {
+    let mut out = String::with_capacity(source.len() * 2);
+    let mut cursor = 0;
+    for token in tokens {
+        if token.start > cursor {
+            out.push_str(&source[cursor..token.start]);
+        }
+        out.push_str(palette.sgr(token.scope));
+        out.push_str(&source[token.start..token.end]);
+        out.push_str(Palette::RESET);
+        cursor = token.end;
+    }
+    if cursor < source.len() {
+        out.push_str(&source[cursor..]);
+    }
+    out
+}
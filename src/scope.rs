@@ -0,0 +1,71 @@
+//! Highlight scopes assigned to spans of source text.
+//!
+//! Scopes are the common vocabulary both lexer backends (regex and
+//! tree-sitter) emit into. Renderers and themes only ever need to know
+//! about `Scope`, never about which backend produced a token.
+
+/// A semantic classification for a span of source text.
+///
+/// Backends are encouraged to be as specific as they can: a tree-sitter
+/// grammar can tell a type *definition* apart from a type *reference*,
+/// while the regex fallback can usually only manage `Type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scope {
+    Keyword,
+    /// A named type anywhere it's referenced (`Config`, `Option<T>`, ...).
+    Type,
+    /// A `struct`/`enum`/`trait` name at its definition site.
+    TypeDefinition,
+    /// A builtin/primitive type (`u32`, `bool`, `str`, ...).
+    TypeBuiltin,
+    /// A function or method name at its definition site.
+    FunctionDefinition,
+    /// A function or method name at a call site.
+    FunctionCall,
+    /// A macro invocation (`println!`, `vec!`, ...).
+    Macro,
+    /// A trait named in a bound (`Ord` in `T: Ord`). Lifetimes in bounds
+    /// and generic parameters are `Lifetime`.
+    TraitBound,
+    /// A lifetime (`'a`, `'static`).
+    Lifetime,
+    /// A field name declared in an enum struct-variant (`reason` in
+    /// `Pending { reason: String }`). Field names at use sites, such as
+    /// the pattern `Status::Pending { reason }`, are not tagged with it.
+    VariantField,
+    String,
+    Number,
+    Comment,
+    Operator,
+    Punctuation,
+    Identifier,
+    /// Anything a backend doesn't have a more specific scope for.
+    Plain,
+}
+
+impl Scope {
+    /// The dotted key themes use to address this scope (e.g.
+    /// `"function.definition"`), matching the vocabulary a theme file's
+    /// `[styles]` table is keyed on.
+    pub fn key(self) -> &'static str {
+        match self {
+            Scope::Keyword => "keyword",
+            Scope::Type => "type",
+            Scope::TypeDefinition => "type.definition",
+            Scope::TypeBuiltin => "type.builtin",
+            Scope::FunctionDefinition => "function.definition",
+            Scope::FunctionCall => "function.call",
+            Scope::Macro => "macro",
+            Scope::TraitBound => "trait.bound",
+            Scope::Lifetime => "lifetime",
+            Scope::VariantField => "variant.field",
+            Scope::String => "string",
+            Scope::Number => "number",
+            Scope::Comment => "comment",
+            Scope::Operator => "operator",
+            Scope::Punctuation => "punctuation",
+            Scope::Identifier => "identifier",
+            Scope::Plain => "plain",
+        }
+    }
+}
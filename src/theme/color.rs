@@ -0,0 +1,185 @@
+//! Color values a theme file can specify: the 16 standard terminal
+//! names, or a `#rrggbb` truecolor hex string.
+
+use serde::de::{self, Deserialize, Deserializer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Named(NamedColor),
+    Rgb(u8, u8, u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NamedColor {
+    Default,
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+}
+
+impl NamedColor {
+    /// The base SGR parameter for this color as a foreground (add 10
+    /// for background).
+    fn sgr_fg(self) -> u8 {
+        match self {
+            NamedColor::Default => 39,
+            NamedColor::Black => 30,
+            NamedColor::Red => 31,
+            NamedColor::Green => 32,
+            NamedColor::Yellow => 33,
+            NamedColor::Blue => 34,
+            NamedColor::Magenta => 35,
+            NamedColor::Cyan => 36,
+            NamedColor::White => 37,
+            NamedColor::BrightBlack => 90,
+            NamedColor::BrightRed => 91,
+            NamedColor::BrightGreen => 92,
+            NamedColor::BrightYellow => 93,
+            NamedColor::BrightBlue => 94,
+            NamedColor::BrightMagenta => 95,
+            NamedColor::BrightCyan => 96,
+            NamedColor::BrightWhite => 97,
+        }
+    }
+}
+
+impl std::str::FromStr for Color {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(hex) = s.strip_prefix('#') {
+            return parse_hex(hex).ok_or_else(|| format!("invalid hex color {s:?}"));
+        }
+        let named = match s {
+            "default" => NamedColor::Default,
+            "black" => NamedColor::Black,
+            "red" => NamedColor::Red,
+            "green" => NamedColor::Green,
+            "yellow" => NamedColor::Yellow,
+            "blue" => NamedColor::Blue,
+            "magenta" => NamedColor::Magenta,
+            "cyan" => NamedColor::Cyan,
+            "white" => NamedColor::White,
+            "bright_black" => NamedColor::BrightBlack,
+            "bright_red" => NamedColor::BrightRed,
+            "bright_green" => NamedColor::BrightGreen,
+            "bright_yellow" => NamedColor::BrightYellow,
+            "bright_blue" => NamedColor::BrightBlue,
+            "bright_magenta" => NamedColor::BrightMagenta,
+            "bright_cyan" => NamedColor::BrightCyan,
+            "bright_white" => NamedColor::BrightWhite,
+            other => return Err(format!("unknown color name {other:?}")),
+        };
+        Ok(Color::Named(named))
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+    Some(Color::Rgb(r, g, b))
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+impl Color {
+    /// Appends this color's SGR parameters (without the leading/trailing
+    /// `\x1b[`/`m`) to `out`, as a foreground if `bg` is false.
+    ///
+    /// Truecolor (`38;2;r;g;b`) is used when the terminal advertises
+    /// support; otherwise an RGB color is quantized down to the nearest
+    /// of the 256-color palette's 6x6x6 color cube.
+    pub fn push_sgr(self, out: &mut String, bg: bool, truecolor: bool) {
+        let offset = if bg { 10 } else { 0 };
+        match self {
+            Color::Named(named) => {
+                out.push_str(&(named.sgr_fg() as u16 + offset as u16).to_string());
+            }
+            Color::Rgb(r, g, b) if truecolor => {
+                let kind = if bg { 48 } else { 38 };
+                out.push_str(&format!("{kind};2;{r};{g};{b}"));
+            }
+            Color::Rgb(r, g, b) => {
+                let kind = if bg { 48 } else { 38 };
+                out.push_str(&format!("{kind};5;{}", quantize_256(r, g, b)));
+            }
+        }
+    }
+}
+
+/// Maps a truecolor RGB value to the nearest index in the xterm
+/// 256-color cube (indices 16..=231, a 6x6x6 grid of channel levels).
+fn quantize_256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |c: u8| -> u8 {
+        // xterm's 6 levels are 0, 95, 135, 175, 215, 255.
+        const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+        LEVELS
+            .iter()
+            .enumerate()
+            .min_by_key(|&(_, &l)| (l as i32 - c as i32).abs())
+            .map(|(i, _)| i as u8)
+            .unwrap_or(0)
+    };
+    16 + 36 * level(r) + 6 * level(g) + level(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hex_and_named_colors() {
+        assert_eq!("#dbbfef".parse(), Ok(Color::Rgb(219, 191, 239)));
+        assert_eq!("magenta".parse(), Ok(Color::Named(NamedColor::Magenta)));
+        assert!("not-a-color".parse::<Color>().is_err());
+    }
+
+    #[test]
+    fn truecolor_emits_24bit_escape() {
+        let mut out = String::new();
+        Color::Rgb(219, 191, 239).push_sgr(&mut out, false, true);
+        assert_eq!(out, "38;2;219;191;239");
+    }
+
+    #[test]
+    fn degrades_to_256_color_without_truecolor_support() {
+        let mut out = String::new();
+        Color::Rgb(219, 191, 239).push_sgr(&mut out, false, false);
+        assert_eq!(out, "38;5;183");
+    }
+
+    #[test]
+    fn named_color_is_unaffected_by_truecolor_support() {
+        let mut with_tc = String::new();
+        let mut without_tc = String::new();
+        Color::Named(NamedColor::Blue).push_sgr(&mut with_tc, false, true);
+        Color::Named(NamedColor::Blue).push_sgr(&mut without_tc, false, false);
+        assert_eq!(with_tc, "34");
+        assert_eq!(without_tc, "34");
+    }
+}
@@ -0,0 +1,228 @@
+//! Theme system: maps each [`Scope`] to a configurable [`Style`],
+//! loaded from a TOML or JSON theme file, or one of the built-in
+//! themes shipped in `themes/`.
+
+mod color;
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+pub use color::Color;
+
+use crate::scope::Scope;
+
+const BUILTIN_DEFAULT: &str = include_str!("../../themes/default.toml");
+const BUILTIN_LILAC: &str = include_str!("../../themes/lilac.toml");
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    #[serde(default)]
+    pub bold: bool,
+    #[serde(default)]
+    pub italic: bool,
+    #[serde(default)]
+    pub underline: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Theme {
+    pub name: String,
+    #[serde(default, rename = "styles")]
+    styles: HashMap<String, Style>,
+}
+
+impl Theme {
+    /// Looks up one of the themes shipped with ultra by name.
+    pub fn builtin(name: &str) -> Option<Theme> {
+        let toml = match name {
+            "default" => BUILTIN_DEFAULT,
+            "lilac" => BUILTIN_LILAC,
+            _ => return None,
+        };
+        Some(toml::from_str(toml).expect("built-in themes are valid"))
+    }
+
+    /// Loads a theme from a TOML or JSON file on disk, picked by its
+    /// extension.
+    pub fn load(path: &Path) -> Result<Theme> {
+        let text =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        if path.extension().and_then(|e| e.to_str()) == Some("json") {
+            serde_json::from_str(&text).with_context(|| format!("parsing {}", path.display()))
+        } else {
+            toml::from_str(&text).with_context(|| format!("parsing {}", path.display()))
+        }
+    }
+
+    /// Resolves a `--theme` argument: a built-in theme name if one
+    /// matches, otherwise a path to a theme file on disk.
+    pub fn resolve(name_or_path: &str) -> Result<Theme> {
+        if let Some(theme) = Theme::builtin(name_or_path) {
+            return Ok(theme);
+        }
+        let path = Path::new(name_or_path);
+        if !path.exists() {
+            bail!("no built-in theme or theme file named {name_or_path:?}");
+        }
+        Theme::load(path)
+    }
+
+    fn style(&self, scope: Scope) -> Style {
+        self.styles.get(scope.key()).copied().unwrap_or_default()
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::builtin("default").expect("built-in default theme is valid")
+    }
+}
+
+/// A theme plus the terminal's actual color capability, producing the
+/// SGR escape sequences a renderer writes for each scope.
+///
+/// The SGR string for each scope only depends on the theme and the
+/// terminal's color capability, both fixed for the process's lifetime,
+/// so they're computed once here rather than on every token rendered.
+#[derive(Clone)]
+pub struct Palette {
+    theme: Theme,
+    sgr_cache: HashMap<&'static str, String>,
+}
+
+impl Palette {
+    pub fn new(theme: Theme) -> Self {
+        let truecolor = supports_truecolor();
+        let sgr_cache = ALL_SCOPES
+            .iter()
+            .map(|&scope| (scope.key(), Palette::render_sgr(&theme, scope, truecolor)))
+            .collect();
+        Palette { theme, sgr_cache }
+    }
+
+    fn render_sgr(theme: &Theme, scope: Scope, truecolor: bool) -> String {
+        let style = theme.style(scope);
+        let mut params = Vec::new();
+        if style.bold {
+            params.push("1".to_string());
+        }
+        if style.italic {
+            params.push("3".to_string());
+        }
+        if style.underline {
+            params.push("4".to_string());
+        }
+        if let Some(fg) = style.fg {
+            let mut s = String::new();
+            fg.push_sgr(&mut s, false, truecolor);
+            params.push(s);
+        }
+        if let Some(bg) = style.bg {
+            let mut s = String::new();
+            bg.push_sgr(&mut s, true, truecolor);
+            params.push(s);
+        }
+        format!("\x1b[{}m", params.join(";"))
+    }
+
+    /// The full `\x1b[...m` escape sequence to switch into `scope`'s style.
+    pub fn sgr(&self, scope: Scope) -> &str {
+        &self.sgr_cache[scope.key()]
+    }
+
+    pub const RESET: &'static str = "\x1b[0m";
+
+    /// The name of the underlying theme, for display in status lines.
+    pub fn name(&self) -> &str {
+        &self.theme.name
+    }
+}
+
+const ALL_SCOPES: [Scope; 17] = [
+    Scope::Keyword,
+    Scope::Type,
+    Scope::TypeDefinition,
+    Scope::TypeBuiltin,
+    Scope::FunctionDefinition,
+    Scope::FunctionCall,
+    Scope::Macro,
+    Scope::TraitBound,
+    Scope::Lifetime,
+    Scope::VariantField,
+    Scope::String,
+    Scope::Number,
+    Scope::Comment,
+    Scope::Operator,
+    Scope::Punctuation,
+    Scope::Identifier,
+    Scope::Plain,
+];
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::new(Theme::default())
+    }
+}
+
+/// A terminal advertises 24-bit color support via `COLORTERM`
+/// (`truecolor` or `24bit`); anything else degrades to 256-color.
+fn supports_truecolor() -> bool {
+    std::env::var("COLORTERM").is_ok_and(|v| v == "truecolor" || v == "24bit")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_themes_parse_and_cover_every_scope() {
+        for name in ["default", "lilac"] {
+            let theme = Theme::builtin(name).unwrap();
+            for scope in [
+                Scope::Keyword,
+                Scope::Type,
+                Scope::TypeDefinition,
+                Scope::TypeBuiltin,
+                Scope::FunctionDefinition,
+                Scope::FunctionCall,
+                Scope::Macro,
+                Scope::TraitBound,
+                Scope::Lifetime,
+                Scope::VariantField,
+                Scope::String,
+                Scope::Number,
+                Scope::Comment,
+                Scope::Operator,
+                Scope::Punctuation,
+                Scope::Identifier,
+                Scope::Plain,
+            ] {
+                assert!(
+                    theme.styles.contains_key(scope.key()),
+                    "{name} theme is missing a style for {:?}",
+                    scope.key()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_theme_name_and_missing_file_both_fail_to_resolve() {
+        assert!(Theme::resolve("not-a-real-theme").is_err());
+    }
+
+    #[test]
+    fn missing_style_falls_back_to_an_empty_default() {
+        let theme = Theme {
+            name: "empty".to_string(),
+            styles: HashMap::new(),
+        };
+        let palette = Palette::new(theme);
+        assert_eq!(palette.sgr(Scope::Keyword), "\x1b[m");
+    }
+}
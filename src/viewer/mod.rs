@@ -0,0 +1,264 @@
+//! Interactive terminal viewer for a highlighted buffer.
+//!
+//! Renders the file a screenful at a time and supports `/`-style
+//! incremental regex search, mirroring the abort/commit semantics of a
+//! typical editor search prompt.
+
+mod search;
+
+use std::io::{self, Write};
+
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::{cursor, execute, queue, terminal};
+
+use crate::highlight::Token;
+use crate::theme::Palette;
+use search::SearchState;
+
+/// Byte range of each line in `source`, excluding its trailing `\n`.
+fn line_ranges(source: &str) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    for (i, b) in source.bytes().enumerate() {
+        if b == b'\n' {
+            ranges.push((start, i));
+            start = i + 1;
+        }
+    }
+    ranges.push((start, source.len()));
+    ranges
+}
+
+/// Puts the terminal into raw mode + the alternate screen for the
+/// viewer's lifetime, and always restores it on drop (including on an
+/// early return via `?`).
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+pub struct Viewer {
+    source: String,
+    tokens: Vec<Token>,
+    lines: Vec<(usize, usize)>,
+    scroll: usize,
+    cursor: usize,
+    search: Option<SearchState>,
+    palette: Palette,
+}
+
+impl Viewer {
+    pub fn new(source: String, tokens: Vec<Token>, palette: Palette) -> Self {
+        let lines = line_ranges(&source);
+        Viewer {
+            source,
+            tokens,
+            lines,
+            scroll: 0,
+            cursor: 0,
+            search: None,
+            palette,
+        }
+    }
+
+    /// Takes over the terminal and runs the viewer's event loop until
+    /// the user quits.
+    pub fn run(&mut self) -> Result<()> {
+        let _guard = TerminalGuard::enter()?;
+        loop {
+            self.draw()?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind == KeyEventKind::Press && !self.handle_key(key.code) {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Returns `false` when the viewer should quit.
+    fn handle_key(&mut self, code: KeyCode) -> bool {
+        if self.search.is_some() {
+            self.handle_search_key(code);
+            return true;
+        }
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('/') => self.search = Some(SearchState::open(self.scroll, self.cursor)),
+            KeyCode::Down | KeyCode::Char('j') => self.move_cursor(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_cursor(-1),
+            KeyCode::PageDown => self.move_cursor(self.page_size() as isize),
+            KeyCode::PageUp => self.move_cursor(-(self.page_size() as isize)),
+            _ => {}
+        }
+        true
+    }
+
+    fn handle_search_key(&mut self, code: KeyCode) {
+        let Some(search) = &mut self.search else {
+            return;
+        };
+        match code {
+            KeyCode::Esc => {
+                let snap = search.snapshot();
+                self.scroll = snap.scroll;
+                self.cursor = snap.cursor;
+                self.search = None;
+            }
+            KeyCode::Enter => {
+                if let Some(&(start, _)) = search.matches().first() {
+                    let line = self.line_at(start);
+                    self.cursor = line;
+                    self.scroll_to_cursor();
+                }
+                self.search = None;
+            }
+            KeyCode::Backspace => search.pop_char(&self.source),
+            KeyCode::Char(c) => search.push_char(c, &self.source),
+            _ => {}
+        }
+    }
+
+    fn line_at(&self, byte_offset: usize) -> usize {
+        self.lines
+            .partition_point(|&(start, _)| start <= byte_offset)
+            .saturating_sub(1)
+    }
+
+    fn page_size(&self) -> usize {
+        terminal::size().map(|(_, h)| h.saturating_sub(1) as usize).unwrap_or(24)
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        let max = self.lines.len().saturating_sub(1);
+        self.cursor = (self.cursor as isize + delta).clamp(0, max as isize) as usize;
+        self.scroll_to_cursor();
+    }
+
+    fn scroll_to_cursor(&mut self) {
+        let height = self.page_size();
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        } else if self.cursor >= self.scroll + height {
+            self.scroll = self.cursor + 1 - height;
+        }
+    }
+
+    fn draw(&self) -> Result<()> {
+        let mut stdout = io::stdout();
+        let height = self.page_size();
+        queue!(stdout, cursor::MoveTo(0, 0))?;
+        for row in 0..height {
+            queue!(stdout, cursor::MoveTo(0, row as u16), terminal::Clear(terminal::ClearType::CurrentLine))?;
+            let line_idx = self.scroll + row;
+            if let Some(&(start, end)) = self.lines.get(line_idx) {
+                let overlay = self.overlay_for_line(start, end);
+                write!(stdout, "{}", self.render_line(start, end, &overlay))?;
+            } else {
+                write!(stdout, "~")?;
+            }
+        }
+        let status_row = height as u16;
+        queue!(stdout, cursor::MoveTo(0, status_row), terminal::Clear(terminal::ClearType::CurrentLine))?;
+        if let Some(search) = &self.search {
+            write!(stdout, "/{}", search.pattern())?;
+        } else {
+            write!(
+                stdout,
+                "-- line {}/{} [{}] (q to quit, / to search) --",
+                self.cursor + 1,
+                self.lines.len(),
+                self.palette.name()
+            )?;
+        }
+        stdout.flush()?;
+        Ok(())
+    }
+
+    fn overlay_for_line(&self, line_start: usize, line_end: usize) -> Vec<(usize, usize)> {
+        let Some(search) = &self.search else {
+            return Vec::new();
+        };
+        search
+            .matches()
+            .iter()
+            .filter(|&&(s, e)| s < line_end && e > line_start)
+            .map(|&(s, e)| (s.max(line_start), e.min(line_end)))
+            .collect()
+    }
+
+    fn render_line(&self, line_start: usize, line_end: usize, overlay: &[(usize, usize)]) -> String {
+        let text = &self.source[line_start..line_end];
+        let mut out = String::new();
+        let mut pos = line_start;
+        while pos < line_end {
+            let scope = self
+                .tokens
+                .iter()
+                .find(|t| t.start <= pos && pos < t.end)
+                .map(|t| t.scope);
+            let in_overlay = overlay.iter().any(|&(s, e)| s <= pos && pos < e);
+
+            let mut end = line_end;
+            for t in &self.tokens {
+                if t.start > pos && t.start < end {
+                    end = t.start;
+                }
+                if t.end > pos && t.end < end {
+                    end = t.end;
+                }
+            }
+            for &(s, e) in overlay {
+                if s > pos && s < end {
+                    end = s;
+                }
+                if e > pos && e < end {
+                    end = e;
+                }
+            }
+
+            if let Some(sc) = scope {
+                out.push_str(self.palette.sgr(sc));
+            }
+            if in_overlay {
+                out.push_str("\x1b[7m");
+            }
+            out.push_str(&text[pos - line_start..end - line_start]);
+            out.push_str(Palette::RESET);
+            pos = end;
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_lines_on_newlines_without_trailing_newline_byte() {
+        let ranges = line_ranges("abc\nde\nf");
+        assert_eq!(ranges, vec![(0, 3), (4, 6), (7, 8)]);
+    }
+
+    #[test]
+    fn line_at_maps_byte_offset_to_line_index() {
+        let v = Viewer::new("abc\nde\nf".to_string(), Vec::new(), Palette::default());
+        assert_eq!(v.line_at(0), 0);
+        assert_eq!(v.line_at(5), 1);
+        assert_eq!(v.line_at(7), 2);
+    }
+}
@@ -0,0 +1,108 @@
+//! Incremental regex search state for the [`super::Viewer`].
+//!
+//! Kept free of any terminal I/O so the match/abort/commit behavior can
+//! be tested directly against strings.
+
+use regex::Regex;
+
+/// Scroll position and cursor line to return to if a search is aborted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Snapshot {
+    pub scroll: usize,
+    pub cursor: usize,
+}
+
+/// An open search prompt: the typed pattern, and the most recent set of
+/// valid matches (byte ranges into the viewer's source).
+pub struct SearchState {
+    pattern: String,
+    matches: Vec<(usize, usize)>,
+    snapshot: Snapshot,
+}
+
+impl SearchState {
+    /// Opens a prompt, snapshotting `scroll`/`cursor` so [`Self::snapshot`]
+    /// can be used to restore them if the search is aborted.
+    pub fn open(scroll: usize, cursor: usize) -> Self {
+        SearchState {
+            pattern: String::new(),
+            matches: Vec::new(),
+            snapshot: Snapshot { scroll, cursor },
+        }
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Current highlight set: the latest matches from a pattern that
+    /// compiled, or the previous valid set if the pattern is currently
+    /// empty or fails to compile.
+    pub fn matches(&self) -> &[(usize, usize)] {
+        &self.matches
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        self.snapshot
+    }
+
+    pub fn push_char(&mut self, c: char, source: &str) {
+        self.pattern.push(c);
+        self.recompute(source);
+    }
+
+    pub fn pop_char(&mut self, source: &str) {
+        self.pattern.pop();
+        self.recompute(source);
+    }
+
+    /// Recompiles the pattern and refreshes `matches`. Empty input is
+    /// skipped outright, and a pattern that fails to compile (e.g. a
+    /// dangling `(`) leaves the last valid highlight state in place
+    /// rather than clearing it.
+    fn recompute(&mut self, source: &str) {
+        if self.pattern.is_empty() {
+            return;
+        }
+        if let Ok(re) = Regex::new(&self.pattern) {
+            self.matches = re.find_iter(source).map(|m| (m.start(), m.end())).collect();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_pattern_keeps_last_matches() {
+        let mut s = SearchState::open(0, 0);
+        s.push_char('x', "xx yy xx");
+        assert_eq!(s.matches(), &[(0, 1), (1, 2), (6, 7), (7, 8)]);
+        s.pop_char("xx yy xx");
+        // back to empty pattern: skipped, so the last valid state (the
+        // single-`x` matches) is kept rather than cleared.
+        assert_eq!(s.matches(), &[(0, 1), (1, 2), (6, 7), (7, 8)]);
+    }
+
+    #[test]
+    fn invalid_regex_keeps_last_valid_matches() {
+        let mut s = SearchState::open(0, 0);
+        s.push_char('x', "xx yy xx");
+        let before = s.matches().to_vec();
+        s.push_char('(', "xx yy xx"); // "x(" is an unterminated group
+        assert_eq!(s.matches(), &before[..]);
+    }
+
+    #[test]
+    fn snapshot_is_fixed_at_open_time() {
+        let s = SearchState::open(5, 12);
+        assert_eq!(
+            s.snapshot(),
+            Snapshot {
+                scroll: 5,
+                cursor: 12
+            }
+        );
+    }
+}